@@ -0,0 +1,261 @@
+//! A lossless concrete syntax tree over an MCNP deck, in the spirit of the
+//! libsyntax2 / rust-analyzer design.
+//!
+//! Every byte of the source is attached to some node as either a
+//! significant token or trivia: continuation joins (`&\n` or a
+//! five-space-indented line), comment cards, and inline `$` comments are
+//! all recorded as explicit trivia nodes rather than being silently
+//! merged away or fed into the token stream. Walking the tree and
+//! concatenating every leaf's text via `to_source` reproduces the input
+//! exactly, so a higher layer can mutate one card's tokens while leaving
+//! everything else — comments, indentation, trailing whitespace —
+//! byte-for-byte untouched.
+
+use chumsky::prelude::*;
+
+use super::Extra;
+
+/// What role a node or leaf plays in the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyntaxKind {
+    /// The whole deck.
+    Root,
+    /// One card, with any continuation joins and comments folded in as
+    /// trivia children.
+    Card,
+    /// A run of significant (non-trivia) text.
+    Token,
+    /// An `&\n` or five-space-indented continuation join.
+    Continuation,
+    /// The newline separating one card from the next.
+    Newline,
+    /// A full-line comment card (columns 1-5 hold `c`/`C`, then a space
+    /// or end of line).
+    CommentCard,
+    /// An inline `$` comment, running to the end of its line.
+    InlineComment,
+}
+
+/// A node in the lossless syntax tree. Leaves carry their own source text
+/// directly; interior nodes carry it only through their children.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntaxNode<'src> {
+    pub kind: SyntaxKind,
+    pub text: Option<&'src str>,
+    pub children: Vec<SyntaxNode<'src>>,
+}
+
+impl<'src> SyntaxNode<'src> {
+    fn leaf(kind: SyntaxKind, text: &'src str) -> Self {
+        SyntaxNode {
+            kind,
+            text: Some(text),
+            children: Vec::new(),
+        }
+    }
+
+    fn node(kind: SyntaxKind, children: Vec<SyntaxNode<'src>>) -> Self {
+        SyntaxNode {
+            kind,
+            text: None,
+            children,
+        }
+    }
+
+    /// Reconstructs the exact source text this node was parsed from by
+    /// concatenating every leaf beneath it, in order.
+    pub fn to_source(&self) -> String {
+        match self.text {
+            Some(text) => text.to_string(),
+            None => self.children.iter().map(SyntaxNode::to_source).collect(),
+        }
+    }
+}
+
+/// A continuation join, kept as an explicit trivia leaf rather than being
+/// silently folded into the surrounding text.
+fn continuation_trivia<'src>() -> impl Parser<'src, &'src str, SyntaxNode<'src>, Extra<'src>> {
+    choice((just("\n     "), just("&\n")))
+        .to_slice()
+        .map(|text| SyntaxNode::leaf(SyntaxKind::Continuation, text))
+}
+
+/// An inline comment: an unquoted `$` followed by everything up to (but
+/// not including) the end of the line. `$` inside the comment text is
+/// just more comment text.
+fn inline_comment<'src>() -> impl Parser<'src, &'src str, SyntaxNode<'src>, Extra<'src>> {
+    just('$')
+        .then(none_of("\n").repeated())
+        .to_slice()
+        .map(|text| SyntaxNode::leaf(SyntaxKind::InlineComment, text))
+}
+
+/// A run of significant text: one or more characters that are neither a
+/// card-boundary newline, the start of a continuation join, nor the
+/// start of an inline comment.
+fn significant_text<'src>() -> impl Parser<'src, &'src str, SyntaxNode<'src>, Extra<'src>> {
+    none_of("\n$")
+        .and_is(just("&\n").not())
+        .repeated()
+        .at_least(1)
+        .to_slice()
+        .map(|text| SyntaxNode::leaf(SyntaxKind::Token, text))
+}
+
+/// One unit of a card's content between continuation joins: significant
+/// text optionally followed by an inline comment, or a bare inline
+/// comment on its own.
+fn content_unit<'src>() -> impl Parser<'src, &'src str, Vec<SyntaxNode<'src>>, Extra<'src>> {
+    choice((
+        significant_text()
+            .then(inline_comment().or_not())
+            .map(|(text, comment)| match comment {
+                Some(comment) => vec![text, comment],
+                None => vec![text],
+            }),
+        inline_comment().map(|comment| vec![comment]),
+    ))
+}
+
+/// One card: an alternation of content units and continuation trivia,
+/// stopping just before the boundary newline that starts the next card.
+/// The leading content unit is optional so a blank line — including the
+/// empty final "card" after a deck's trailing newline — parses as a
+/// card with no children instead of failing outright.
+fn card<'src>() -> impl Parser<'src, &'src str, SyntaxNode<'src>, Extra<'src>> {
+    content_unit()
+        .or_not()
+        .then(
+            continuation_trivia()
+                .then(content_unit().or_not())
+                .repeated()
+                .collect::<Vec<_>>(),
+        )
+        .map(|(first, rest)| {
+            let mut children = first.unwrap_or_default();
+            for (continuation, unit) in rest {
+                children.push(continuation);
+                children.extend(unit.into_iter().flatten());
+            }
+            SyntaxNode::node(SyntaxKind::Card, children)
+        })
+}
+
+/// A full-line comment card: up to four blank columns, then `c` or `C`
+/// in columns 1-5, then a space or the end of the line, then the rest of
+/// the line as comment text. This only ever runs at a card boundary —
+/// a `c` that starts a continuation line is already consumed by
+/// `continuation_trivia` before this parser sees it, so it can't be
+/// mistaken for a comment card.
+fn comment_card<'src>() -> impl Parser<'src, &'src str, SyntaxNode<'src>, Extra<'src>> {
+    just(' ')
+        .repeated()
+        .at_most(4)
+        .then(one_of("cC"))
+        .then(choice((
+            just(' ').ignored(),
+            just('\n').ignored().rewind(),
+            end(),
+        )))
+        .then(none_of("\n").repeated())
+        .to_slice()
+        .map(|text| SyntaxNode::leaf(SyntaxKind::CommentCard, text))
+}
+
+/// The boundary newline between one card and the next, kept as an
+/// explicit trivia leaf rather than being consumed silently.
+fn newline_trivia<'src>() -> impl Parser<'src, &'src str, SyntaxNode<'src>, Extra<'src>> {
+    just('\n')
+        .to_slice()
+        .map(|text| SyntaxNode::leaf(SyntaxKind::Newline, text))
+}
+
+/// One entry at the deck level: a comment card or a real card. Comment
+/// cards are tried first since they're the more specific shape; anything
+/// that doesn't fit falls through to an ordinary card.
+fn entry<'src>() -> impl Parser<'src, &'src str, SyntaxNode<'src>, Extra<'src>> {
+    choice((comment_card(), card()))
+}
+
+/// Moves each `CommentCard` entry (and the newline trivia following it)
+/// into the front of the next `Card`'s children, so comments attach to
+/// the card they describe instead of floating at the deck level. Comment
+/// cards with no following card (trailing comments at end of deck) are
+/// left where they are. This only reshapes the tree; `to_source` is
+/// unaffected, since the same leaves are visited in the same order.
+fn attach_comment_trivia(root: SyntaxNode<'_>) -> SyntaxNode<'_> {
+    let mut children = Vec::with_capacity(root.children.len());
+    let mut pending = Vec::new();
+    for child in root.children {
+        match child.kind {
+            SyntaxKind::CommentCard => pending.push(child),
+            SyntaxKind::Newline if !pending.is_empty() => pending.push(child),
+            SyntaxKind::Card => {
+                let mut merged = std::mem::take(&mut pending);
+                merged.extend(child.children);
+                children.push(SyntaxNode::node(SyntaxKind::Card, merged));
+            }
+            _ => {
+                children.append(&mut pending);
+                children.push(child);
+            }
+        }
+    }
+    children.append(&mut pending);
+    SyntaxNode::node(SyntaxKind::Root, children)
+}
+
+/// Parses a whole deck into a lossless syntax tree.
+pub fn cst_parser<'src>() -> impl Parser<'src, &'src str, SyntaxNode<'src>, Extra<'src>> {
+    entry()
+        .then(
+            newline_trivia()
+                .then(entry())
+                .repeated()
+                .collect::<Vec<_>>(),
+        )
+        .map(|(first, rest)| {
+            let mut children = vec![first];
+            for (newline, entry) in rest {
+                children.push(newline);
+                children.push(entry);
+            }
+            attach_comment_trivia(SyntaxNode::node(SyntaxKind::Root, children))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> SyntaxNode<'_> {
+        cst_parser().parse(input).into_result().unwrap()
+    }
+
+    #[test]
+    fn comment_card_attaches_to_the_following_card() {
+        let tree = parse("c a leading comment\n1 0 -2");
+        assert_eq!(tree.children[0].kind, SyntaxKind::Card);
+        assert_eq!(tree.children[0].children[0].kind, SyntaxKind::CommentCard);
+    }
+
+    #[test]
+    fn inline_comment_is_trailing_trivia_not_a_token() {
+        let tree = parse("1 0 -2 $ note");
+        let card = &tree.children[0];
+        let kinds: Vec<_> = card.children.iter().map(|c| c.kind).collect();
+        assert_eq!(kinds, vec![SyntaxKind::Token, SyntaxKind::InlineComment]);
+    }
+
+    #[test]
+    fn to_source_round_trips_comments() {
+        let input = "c header\n1 0 -2 $ note";
+        assert_eq!(parse(input).to_source(), input);
+    }
+
+    #[test]
+    fn parses_input_ending_in_a_trailing_newline() {
+        let input = "hello\nworld\n";
+        assert_eq!(parse(input).to_source(), input);
+    }
+}
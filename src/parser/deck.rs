@@ -0,0 +1,211 @@
+//! A structured, three-block document model for an MCNP deck.
+//!
+//! An MCNP input file is a title line followed by three blank-line
+//! delimited blocks: cell cards, surface cards, and data cards. This
+//! module sits above the continuation-joining and recovery done by
+//! [`super::parse_input`] and turns its flat, recovered sequence of
+//! cards into [`Deck`], so downstream code can work with `deck.cells`,
+//! `deck.surfaces`, and `deck.data` directly instead of re-deriving
+//! block membership from blank lines every time.
+
+use super::{parse_input, InputError};
+
+/// A cell card: its cell number and the remaining tokens (material,
+/// density, geometry, importances, ...) for a later stage to interpret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellCard<'src> {
+    pub number: u32,
+    pub tokens: Vec<&'src str>,
+}
+
+/// A surface card: its surface number and the remaining tokens (the
+/// surface mnemonic and its coefficients).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurfaceCard<'src> {
+    pub number: u32,
+    pub tokens: Vec<&'src str>,
+}
+
+/// A data card. `number` is the leading digit run of the card's first
+/// token, e.g. `1` for a `M1` material card; it's `0` for data cards
+/// whose first token is a bare mnemonic, like `SDEF` or `MODE`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataCard<'src> {
+    pub number: u32,
+    pub tokens: Vec<&'src str>,
+}
+
+/// An MCNP deck split into its title and three card blocks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deck<'src> {
+    pub title: &'src str,
+    pub cells: Vec<CellCard<'src>>,
+    pub surfaces: Vec<SurfaceCard<'src>>,
+    pub data: Vec<DataCard<'src>>,
+    /// Cards that failed to parse, recovered from by [`super::parse_input`].
+    pub errors: Vec<InputError>,
+}
+
+/// Whether `text` is a full-line comment card: up to four blank columns,
+/// then `c` or `C` in columns 1-5, then a space or the end of the line.
+/// Mirrors the `cst` module's `comment_card` shape, since both recognize
+/// the same MCNP convention; this one works over an already-joined
+/// card's flat text rather than building a syntax node.
+fn is_comment_card(text: &str) -> bool {
+    let indent = text.len() - text.trim_start_matches(' ').len();
+    if indent > 4 {
+        return false;
+    }
+    let mut rest = text[indent..].chars();
+    match rest.next() {
+        Some('c') | Some('C') => matches!(rest.next(), None | Some(' ')),
+        _ => false,
+    }
+}
+
+/// Splits an already-joined card's text into whitespace-delimited
+/// tokens, also treating a bare `&` as a separator so a stripped
+/// continuation marker can't glue two tokens together.
+fn tokenize(text: &str) -> Vec<&str> {
+    text.split(|c: char| c.is_whitespace() || c == '&')
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+/// The leading run of ASCII digits in a token, e.g. `1` from `M1` or
+/// `20` from `20`; `0` if the token has no leading digits once any
+/// non-digit prefix is stripped.
+fn leading_number(token: &str) -> u32 {
+    token
+        .trim_start_matches(|c: char| !c.is_ascii_digit())
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>()
+        .parse()
+        .unwrap_or(0)
+}
+
+fn cell_card(tokens: Vec<&str>) -> Option<CellCard<'_>> {
+    let (&first, rest) = tokens.split_first()?;
+    Some(CellCard {
+        number: leading_number(first),
+        tokens: rest.to_vec(),
+    })
+}
+
+fn surface_card(tokens: Vec<&str>) -> Option<SurfaceCard<'_>> {
+    let (&first, rest) = tokens.split_first()?;
+    Some(SurfaceCard {
+        number: leading_number(first),
+        tokens: rest.to_vec(),
+    })
+}
+
+fn data_card(tokens: Vec<&str>) -> Option<DataCard<'_>> {
+    let (&first, rest) = tokens.split_first()?;
+    Some(DataCard {
+        number: leading_number(first),
+        tokens: rest.to_vec(),
+    })
+}
+
+/// Parses the body of the deck (everything after the title line) into
+/// joined cards, using blank-line "cards" (an empty line is itself a
+/// zero-length card) to mark the boundaries between blocks. Reuses
+/// [`super::parse_input`]'s recovery so one malformed card is reported
+/// through the returned errors instead of emptying the whole deck.
+fn joined_cards(body: &str) -> (Vec<&str>, Vec<InputError>) {
+    let (cards, errors) = parse_input(body);
+    (cards.into_iter().map(|card| card.text).collect(), errors)
+}
+
+/// Parses a full deck: the title line, then the cell, surface, and data
+/// blocks, in order, separated by blank lines.
+pub fn parse_deck(input: &str) -> Deck<'_> {
+    let (title, body) = input.split_once('\n').unwrap_or((input, ""));
+    let (cards, mut errors) = joined_cards(body);
+    // `joined_cards` parses only the body, so shift its spans past the
+    // title line to make them offsets into `input` itself.
+    let body_offset = input.len() - body.len();
+    for error in &mut errors {
+        error.span.start += body_offset;
+        error.span.end += body_offset;
+    }
+
+    let mut blocks: Vec<Vec<&str>> = vec![Vec::new()];
+    for text in cards {
+        if text.trim().is_empty() {
+            blocks.push(Vec::new());
+        } else if is_comment_card(text) {
+            // Comment cards describe the deck; they aren't deck data and
+            // don't delimit a block the way a blank line does.
+            continue;
+        } else {
+            blocks.last_mut().expect("always pushed at least one block").push(text);
+        }
+    }
+    let mut blocks = blocks.into_iter();
+
+    let cells = blocks
+        .next()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|text| cell_card(tokenize(text)))
+        .collect();
+    let surfaces = blocks
+        .next()
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|text| surface_card(tokenize(text)))
+        .collect();
+    // Any later block is part of the data block; MCNP doesn't expect a
+    // further blank-line split, but merging rather than discarding keeps
+    // this honest about decks that have one anyway.
+    let data = blocks
+        .flatten()
+        .filter_map(|text| data_card(tokenize(text)))
+        .collect();
+
+    Deck {
+        title,
+        cells,
+        surfaces,
+        data,
+        errors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn five_space_continuation_keeps_the_continued_tokens_on_one_cell_card() {
+        let deck = parse_deck("Test problem\n1 0 -2 -3 -4\n     imp:n=1\n");
+        assert_eq!(deck.title, "Test problem");
+        assert_eq!(deck.cells.len(), 1);
+        assert_eq!(deck.cells[0].number, 1);
+        assert_eq!(
+            deck.cells[0].tokens,
+            vec!["0", "-2", "-3", "-4", "imp:n=1"]
+        );
+    }
+
+    #[test]
+    fn comment_card_in_a_block_is_not_counted_as_deck_data() {
+        let deck = parse_deck("Title\nc a leading comment\n1 0 -2\n\n2 pz 0\n\nm1 1001 1\n");
+        assert_eq!(deck.cells.len(), 1);
+        assert_eq!(deck.cells[0].number, 1);
+        assert_eq!(deck.surfaces.len(), 1);
+        assert_eq!(deck.data.len(), 1);
+    }
+
+    #[test]
+    fn malformed_card_is_reported_without_emptying_the_rest_of_the_deck() {
+        let deck = parse_deck("Title\n1 0 -2\n\n2 pz 0\n\nbad & card\nm1 1001 1\n");
+        assert_eq!(deck.cells.len(), 1);
+        assert_eq!(deck.surfaces.len(), 1);
+        assert_eq!(deck.data.len(), 1);
+        assert_eq!(deck.errors.len(), 1);
+    }
+}
@@ -0,0 +1,250 @@
+//! Precedence-climbing parser for MCNP cell-card geometry descriptions.
+//!
+//! A geometry description combines surface and cell numbers with three
+//! operators of differing precedence: complement `#` (highest, unary),
+//! intersection (implicit, denoted only by adjacency between atoms), and
+//! union `:` (lowest). For example `1 -2 : 3 #(4 5)` parses as
+//! `((1 ∩ -2) ∪ (3 ∩ ¬(4 ∩ 5)))`.
+
+/// A node in a parsed geometry expression tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeometryExpr {
+    SurfaceRef(i32),
+    CellRef(u32),
+    Complement(Box<GeometryExpr>),
+    Intersection(Box<GeometryExpr>, Box<GeometryExpr>),
+    Union(Box<GeometryExpr>, Box<GeometryExpr>),
+}
+
+/// An error produced while parsing a geometry token run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeometryParseError {
+    /// The token run ended where an atom (a number, `#`, or `(`) was expected.
+    UnexpectedEnd,
+    /// A token was found where an atom was expected.
+    ExpectedAtom(GeomToken),
+    /// A `(` group was never closed.
+    UnclosedGroup,
+    /// `#` was followed by a negative number. Cell references are
+    /// unsigned, so a `-` here is a mistake in the deck, not meaningful
+    /// syntax.
+    NegativeCellRef(i32),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeomToken {
+    Number(i32),
+    Hash,
+    LParen,
+    RParen,
+    Colon,
+}
+
+fn lex(input: &str) -> Vec<GeomToken> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '#' => {
+                chars.next();
+                tokens.push(GeomToken::Hash);
+            }
+            '(' => {
+                chars.next();
+                tokens.push(GeomToken::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(GeomToken::RParen);
+            }
+            ':' => {
+                chars.next();
+                tokens.push(GeomToken::Colon);
+            }
+            '+' | '-' | '0'..='9' => {
+                let mut num = String::new();
+                if c == '+' || c == '-' {
+                    num.push(c);
+                    chars.next();
+                }
+                while let Some(&d) = chars.peek() {
+                    if d.is_ascii_digit() {
+                        num.push(d);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(GeomToken::Number(num.parse().expect(
+                    "only a leading sign and ascii digits were pushed onto `num`",
+                )));
+            }
+            _ => {
+                // Unrecognized characters (stray tabs, etc.) are skipped; the
+                // caller is responsible for validating the card beforehand.
+                chars.next();
+            }
+        }
+    }
+    tokens
+}
+
+/// Binding powers for the two binary operators, `(left, right)`. Intersection
+/// binds tighter than union, and both are left-associative, so the right
+/// binding power is one higher than the left.
+const UNION_BP: (u8, u8) = (1, 2);
+const INTERSECTION_BP: (u8, u8) = (3, 4);
+
+struct TokenStream<'a> {
+    tokens: &'a [GeomToken],
+    pos: usize,
+}
+
+impl<'a> TokenStream<'a> {
+    fn peek(&self) -> Option<&'a GeomToken> {
+        self.tokens.get(self.pos)
+    }
+
+    fn bump(&mut self) -> Option<&'a GeomToken> {
+        let tok = self.tokens.get(self.pos);
+        self.pos += 1;
+        tok
+    }
+
+    /// Whether the next token can begin an atom, i.e. an implicit
+    /// intersection sits between the previous atom and this one.
+    fn at_atom_start(&self) -> bool {
+        matches!(
+            self.peek(),
+            Some(GeomToken::Number(_)) | Some(GeomToken::Hash) | Some(GeomToken::LParen)
+        )
+    }
+
+    fn atom(&mut self) -> Result<GeometryExpr, GeometryParseError> {
+        match self.bump() {
+            Some(GeomToken::Number(n)) => Ok(GeometryExpr::SurfaceRef(*n)),
+            Some(GeomToken::Hash) => match self.peek() {
+                Some(GeomToken::LParen) => {
+                    self.bump();
+                    let inner = self.expr(0)?;
+                    match self.bump() {
+                        Some(GeomToken::RParen) => Ok(GeometryExpr::Complement(Box::new(inner))),
+                        _ => Err(GeometryParseError::UnclosedGroup),
+                    }
+                }
+                Some(GeomToken::Number(n)) => {
+                    let n = *n;
+                    self.bump();
+                    if n.is_negative() {
+                        return Err(GeometryParseError::NegativeCellRef(n));
+                    }
+                    Ok(GeometryExpr::Complement(Box::new(GeometryExpr::CellRef(
+                        n as u32,
+                    ))))
+                }
+                Some(tok) => Err(GeometryParseError::ExpectedAtom(tok.clone())),
+                None => Err(GeometryParseError::UnexpectedEnd),
+            },
+            Some(GeomToken::LParen) => {
+                let inner = self.expr(0)?;
+                match self.bump() {
+                    Some(GeomToken::RParen) => Ok(inner),
+                    _ => Err(GeometryParseError::UnclosedGroup),
+                }
+            }
+            Some(tok) => Err(GeometryParseError::ExpectedAtom(tok.clone())),
+            None => Err(GeometryParseError::UnexpectedEnd),
+        }
+    }
+
+    /// Precedence-climbing core: parses an atom, then repeatedly consumes
+    /// binary operators whose left binding power is at least `min_bp`,
+    /// recursing with the operator's right binding power. Implicit
+    /// intersection has no token of its own, so "another atom starts here"
+    /// is treated as the operator; the loop stops at `:`, `)`, or the end
+    /// of the token run.
+    fn expr(&mut self, min_bp: u8) -> Result<GeometryExpr, GeometryParseError> {
+        let mut lhs = self.atom()?;
+        loop {
+            match self.peek() {
+                Some(GeomToken::Colon) => {
+                    if UNION_BP.0 < min_bp {
+                        break;
+                    }
+                    self.bump();
+                    let rhs = self.expr(UNION_BP.1)?;
+                    lhs = GeometryExpr::Union(Box::new(lhs), Box::new(rhs));
+                }
+                _ if self.at_atom_start() => {
+                    if INTERSECTION_BP.0 < min_bp {
+                        break;
+                    }
+                    let rhs = self.expr(INTERSECTION_BP.1)?;
+                    lhs = GeometryExpr::Intersection(Box::new(lhs), Box::new(rhs));
+                }
+                _ => break,
+            }
+        }
+        Ok(lhs)
+    }
+}
+
+/// Parses the token run from a cell card's geometry field into an
+/// expression tree.
+pub fn parse_geometry(input: &str) -> Result<GeometryExpr, GeometryParseError> {
+    let tokens = lex(input);
+    let mut stream = TokenStream {
+        tokens: &tokens,
+        pos: 0,
+    };
+    let expr = stream.expr(0)?;
+    if stream.pos != tokens.len() {
+        return Err(GeometryParseError::ExpectedAtom(
+            tokens[stream.pos].clone(),
+        ));
+    }
+    Ok(expr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn precedence_and_associativity_match_the_worked_example() {
+        let expr = parse_geometry("1 -2 : 3 #(4 5)").unwrap();
+        let intersection_1_neg2 = GeometryExpr::Intersection(
+            Box::new(GeometryExpr::SurfaceRef(1)),
+            Box::new(GeometryExpr::SurfaceRef(-2)),
+        );
+        let complement_4_5 = GeometryExpr::Complement(Box::new(GeometryExpr::Intersection(
+            Box::new(GeometryExpr::SurfaceRef(4)),
+            Box::new(GeometryExpr::SurfaceRef(5)),
+        )));
+        let intersection_3_complement = GeometryExpr::Intersection(
+            Box::new(GeometryExpr::SurfaceRef(3)),
+            Box::new(complement_4_5),
+        );
+        let expected =
+            GeometryExpr::Union(Box::new(intersection_1_neg2), Box::new(intersection_3_complement));
+        assert_eq!(expr, expected);
+    }
+
+    #[test]
+    fn bare_hash_number_complements_a_cell_reference() {
+        let expr = parse_geometry("#4").unwrap();
+        assert_eq!(
+            expr,
+            GeometryExpr::Complement(Box::new(GeometryExpr::CellRef(4)))
+        );
+    }
+
+    #[test]
+    fn negative_cell_reference_after_hash_is_an_error() {
+        let err = parse_geometry("#-5").unwrap_err();
+        assert_eq!(err, GeometryParseError::NegativeCellRef(-5));
+    }
+}
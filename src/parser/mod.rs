@@ -0,0 +1,178 @@
+use chumsky::prelude::*;
+
+pub mod cst;
+pub mod deck;
+pub mod geometry;
+pub mod span;
+
+use span::LineIndex;
+
+pub(crate) type Extra<'src> = extra::Err<Rich<'src, char>>;
+
+/// A single MCNP card with its continuation lines already joined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card<'src> {
+    pub text: &'src str,
+}
+
+/// A card that failed to parse, recovered from well enough that the rest
+/// of the deck still parses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputError {
+    pub span: SimpleSpan,
+    pub message: String,
+}
+
+impl InputError {
+    /// Renders this error as `line:column: message`, followed by the
+    /// offending physical line with a caret under the exact column,
+    /// using `index` to map this error's span back to the original
+    /// source.
+    pub fn render(&self, index: &LineIndex<'_>) -> String {
+        let (line, column) = index.line_col(self.span.start);
+        format!(
+            "{line}:{column}: {}\n{}",
+            self.message,
+            index.caret_at(self.span.start)
+        )
+    }
+}
+
+/// A continuation join: either an `&` line continuation or a five-space
+/// indented continuation line.
+fn continuation<'src>() -> impl Parser<'src, &'src str, &'src str, Extra<'src>> {
+    choice((just("\n     "), just("&\n")))
+}
+
+/// The boundary between one card and the next: a newline not already
+/// consumed as part of a continuation.
+fn card_boundary<'src>() -> impl Parser<'src, &'src str, (), Extra<'src>> {
+    just('\n').ignored()
+}
+
+/// Where a card is allowed to end: at a card boundary (peeked, not
+/// consumed, so the boundary is still there for `separated_by`) or at
+/// the end of input. If neither holds, whatever stopped the card's
+/// content loop — in practice a stray `&` that isn't part of an `&\n`
+/// continuation — is a malformed card.
+fn at_card_end<'src>() -> impl Parser<'src, &'src str, (), Extra<'src>> {
+    choice((card_boundary().rewind(), end()))
+}
+
+/// The text of one card: a run of characters that are neither `&` nor a
+/// newline, with any continuation lines folded in, stopping just before
+/// the boundary newline that starts the next card. `&` is excluded from
+/// the content runs so that it's only ever consumed as part of an
+/// `&\n` continuation; a bare `&` elsewhere makes the card fail to
+/// parse instead of being silently swallowed.
+pub(crate) fn card<'src>() -> impl Parser<'src, &'src str, Card<'src>, Extra<'src>> {
+    none_of("&\n")
+        .repeated()
+        .then(continuation().then(none_of("&\n").repeated()).repeated())
+        .to_slice()
+        .then_ignore(at_card_end())
+        .map(|text| Card { text })
+}
+
+/// Parses a full deck into its cards.
+///
+/// Each card is wrapped in a recovery strategy: if it fails to parse, the
+/// parser skips forward to the next card boundary, records an
+/// `InputError` for the span it skipped, and resynchronizes so later
+/// cards still parse. The public entry point therefore always returns
+/// everything it could recover, rather than giving up at the first bad
+/// card in the deck.
+///
+/// `recover_with` still leaves the original parse error it recovered from
+/// in chumsky's own error list, so `.parse(..).into_result()` reports
+/// `Err` even on a fully recovered parse; use [`parse_input`] rather than
+/// calling this directly, since it reads the output instead of that
+/// internal error list.
+pub fn input_parser<'src>(
+) -> impl Parser<'src, &'src str, (Vec<Card<'src>>, Vec<InputError>), Extra<'src>> {
+    card()
+        .map(Ok)
+        .recover_with(via_parser(
+            any()
+                .and_is(card_boundary().not())
+                .repeated()
+                .at_least(1)
+                .to_slice()
+                .map_with(|_, e| {
+                    Err(InputError {
+                        span: e.span(),
+                        message: "malformed card".to_string(),
+                    })
+                }),
+        ))
+        .separated_by(card_boundary())
+        .collect::<Vec<_>>()
+        .map(|results| {
+            let mut cards = Vec::new();
+            let mut errors = Vec::new();
+            for result in results {
+                match result {
+                    Ok(card) => cards.push(card),
+                    Err(error) => errors.push(error),
+                }
+            }
+            (cards, errors)
+        })
+}
+
+/// Parses a full deck and returns everything [`input_parser`] could
+/// recover. Every malformed card it hits is reported through the
+/// returned `Vec<InputError>`, not through the `Result` of the parse
+/// itself — `input_parser` always succeeds at the top level by design, so
+/// this reads the output directly via `into_output()` rather than
+/// `into_result()`, which would see the recovered-from errors still
+/// sitting in chumsky's internal error list and report `Err` even though
+/// a usable `(cards, errors)` output exists.
+pub fn parse_input(source: &str) -> (Vec<Card<'_>>, Vec<InputError>) {
+    input_parser().parse(source).into_output().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> (Vec<Card<'_>>, Vec<InputError>) {
+        parse_input(input)
+    }
+
+    #[test]
+    fn ampersand_continuation_joins_into_one_card() {
+        let (cards, errors) = parse("1 0 2 &\n-5 6");
+        assert!(errors.is_empty());
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].text, "1 0 2 &\n-5 6");
+    }
+
+    #[test]
+    fn five_space_indent_continuation_joins_into_one_card() {
+        let (cards, errors) = parse("1 0 -2 -3 -4\n     imp:n=1");
+        assert!(errors.is_empty());
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].text, "1 0 -2 -3 -4\n     imp:n=1");
+    }
+
+    #[test]
+    fn stray_ampersand_is_a_recoverable_malformed_card() {
+        let (cards, errors) = parse("1 0 2\nbad & card\n3 0 4");
+        assert_eq!(cards.len(), 2);
+        assert_eq!(cards[0].text, "1 0 2");
+        assert_eq!(cards[1].text, "3 0 4");
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn render_points_at_the_malformed_card_on_its_own_physical_line() {
+        let source = "1 0 2\nbad & card\n3 0 4";
+        let (_, errors) = parse(source);
+        let index = LineIndex::new(source);
+        assert_eq!(
+            errors[0].render(&index),
+            "2:1: malformed card\nbad & card\n^"
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! Maps byte offsets in the original source back to 1-based `(line,
+//! column)` pairs, in the spirit of pest's `Position`/`line_col`.
+//!
+//! Card text, tokens, and spans produced elsewhere in this crate are
+//! always literal slices of the original source — continuation joins
+//! are preserved as explicit trivia (see [`super::cst`]) rather than
+//! spliced out — so a byte offset recovered from any of them already
+//! lands on the right physical line; there's no separate accounting to
+//! do for a stripped `&` or an injected join newline, only ordinary line
+//! counting over the one source string everything is sliced from.
+
+/// An index of line-start offsets for a single source string, letting
+/// any byte offset into it be turned into a `(line, column)` pair
+/// without rescanning from the start each time.
+pub struct LineIndex<'src> {
+    source: &'src str,
+    line_starts: Vec<usize>,
+}
+
+impl<'src> LineIndex<'src> {
+    pub fn new(source: &'src str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(source.match_indices('\n').map(|(i, _)| i + 1));
+        LineIndex { source, line_starts }
+    }
+
+    /// The 1-based `(line, column)` for a byte offset into the source
+    /// this index was built from.
+    pub fn line_col(&self, offset: usize) -> (usize, usize) {
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        let column = offset - self.line_starts[line] + 1;
+        (line + 1, column)
+    }
+
+    /// The byte offset of `text` within the source, if `text` is
+    /// actually a slice of it (as every `Card`, token, and `to_slice`
+    /// node in this crate is).
+    pub fn offset_of(&self, text: &str) -> Option<usize> {
+        let source_start = self.source.as_ptr() as usize;
+        let source_end = source_start + self.source.len();
+        let text_start = text.as_ptr() as usize;
+        if text_start < source_start || text_start > source_end {
+            return None;
+        }
+        Some(text_start - source_start)
+    }
+
+    /// The 1-based `(line, column)` of the start of `text`, if `text` is
+    /// a slice of the source this index was built from.
+    pub fn line_col_of(&self, text: &str) -> Option<(usize, usize)> {
+        self.offset_of(text).map(|offset| self.line_col(offset))
+    }
+
+    /// Renders the physical line containing `offset`, with a `^` caret
+    /// under the exact column, for diagnostic output.
+    pub fn caret_at(&self, offset: usize) -> String {
+        let (line, column) = self.line_col(offset);
+        let line_start = self.line_starts[line - 1];
+        let line_end = self.source[line_start..]
+            .find('\n')
+            .map(|relative| line_start + relative)
+            .unwrap_or(self.source.len());
+        format!(
+            "{}\n{}^",
+            &self.source[line_start..line_end],
+            " ".repeat(column - 1)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_col_accounts_for_earlier_continuation_and_comment_lines() {
+        let source = "1 0 2 &\n-5 6\nc a comment\n3 0 4\n";
+        let index = LineIndex::new(source);
+        let offset = source.rfind("3 0 4").unwrap();
+        assert_eq!(index.line_col(offset), (4, 1));
+    }
+
+    #[test]
+    fn line_col_of_locates_a_slice_taken_from_the_source() {
+        let source = "1 0 2\n     imp:n=1\n";
+        let index = LineIndex::new(source);
+        let token = &source[source.find("imp:n=1").unwrap()..][.."imp:n=1".len()];
+        assert_eq!(index.line_col_of(token), Some((2, 6)));
+    }
+
+    #[test]
+    fn caret_at_points_under_the_exact_column() {
+        let source = "1 0 2\n3 bad 4\n";
+        let index = LineIndex::new(source);
+        let offset = source.rfind("bad").unwrap();
+        assert_eq!(index.caret_at(offset), "3 bad 4\n  ^");
+    }
+}